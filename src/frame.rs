@@ -0,0 +1,224 @@
+// Copyright (c) 2017-2018, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+use crate::plane::Plane;
+use crate::util::*;
+
+/// Padding, in pixels, added on every side of the luma plane of a `Frame`
+/// built via `Frame::new`. Chroma planes get this scaled down by their own
+/// `xdec`/`ydec`, same as `Plane::new` expects.
+const FRAME_XPAD: usize = 32;
+const FRAME_YPAD: usize = 32;
+
+/// Whether sample values span the full coded range or reserve the
+/// MPEG "studio swing" head/foot room.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelRange {
+  Limited,
+  Full
+}
+
+/// Describes the chroma subsampling, bit depth, and pixel range of a
+/// `Frame`'s planes, in the spirit of VapourSynth's frame/format model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Format {
+  pub xdec: usize,
+  pub ydec: usize,
+  pub bit_depth: usize,
+  pub pixel_range: PixelRange
+}
+
+impl Format {
+  pub const fn new(
+    xdec: usize, ydec: usize, bit_depth: usize, pixel_range: PixelRange
+  ) -> Self {
+    Format { xdec, ydec, bit_depth, pixel_range }
+  }
+}
+
+/// Indexes a `Frame`'s planes by component rather than by raw position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaneIndex {
+  Y = 0,
+  U = 1,
+  V = 2
+}
+
+/// Returned by `Frame::validate_padding` when a plane's padding region does
+/// not match what `Plane::pad` would have produced -- e.g. because the
+/// plane was imported from FFI with stride padding left unspecified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonZeroPadding {
+  pub plane: usize,
+  pub offset: usize
+}
+
+/// The three planes of a coded video frame plus the `Format` describing how
+/// they relate to each other, so callers stop passing loose plane arrays
+/// around and querying `xdec`/`ydec` by hand.
+#[derive(Clone)]
+pub struct Frame<T: Pixel> {
+  pub planes: [Plane<T>; 3],
+  format: Format
+}
+
+/// Rounds `value >> shift` up rather than truncating, so odd luma
+/// dimensions still get a chroma plane large enough to cover them.
+fn ceil_shr(value: usize, shift: usize) -> usize {
+  (value + (1 << shift) - 1) >> shift
+}
+
+impl<T: Pixel> Frame<T> {
+  /// Builds a new frame of `width`x`height` luma samples, allocating all
+  /// three planes with the subsampling from `format` and shared padding.
+  pub fn new(format: Format, width: usize, height: usize) -> Self {
+    let y = Plane::new(width, height, 0, 0, FRAME_XPAD, FRAME_YPAD);
+    let chroma_width = ceil_shr(width, format.xdec);
+    let chroma_height = ceil_shr(height, format.ydec);
+    let new_chroma = || {
+      Plane::new(
+        chroma_width,
+        chroma_height,
+        format.xdec,
+        format.ydec,
+        FRAME_XPAD >> format.xdec,
+        FRAME_YPAD >> format.ydec
+      )
+    };
+    Frame { planes: [y, new_chroma(), new_chroma()], format }
+  }
+
+  pub fn format(&self) -> Format {
+    self.format
+  }
+
+  pub fn plane(&self, idx: usize) -> &Plane<T> {
+    &self.planes[idx]
+  }
+
+  pub fn plane_mut(&mut self, idx: usize) -> &mut Plane<T> {
+    &mut self.planes[idx]
+  }
+
+  /// Typed access to a single component by `PlaneIndex` rather than a raw
+  /// plane index. Planes are always stored in Y, U, V order, so the lookup
+  /// does not depend on `self.format()`.
+  pub fn component(&self, idx: PlaneIndex) -> &Plane<T> {
+    &self.planes[idx as usize]
+  }
+
+  pub fn component_mut(&mut self, idx: PlaneIndex) -> &mut Plane<T> {
+    &mut self.planes[idx as usize]
+  }
+
+  /// Checks that every plane's padding region holds the border-replicated
+  /// values `Plane::pad` would have produced, rather than leftover garbage.
+  /// This matters when a `Frame` is built from an externally supplied
+  /// buffer (e.g. over FFI) whose stride padding is unspecified.
+  pub fn validate_padding(&self) -> Result<(), NonZeroPadding> {
+    for (idx, plane) in self.planes.iter().enumerate() {
+      validate_plane_padding(plane)
+        .map_err(|offset| NonZeroPadding { plane: idx, offset })?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn new_sizes_chroma_planes_from_subsampled_dimensions() {
+    // 15x9 luma with 4:2:0 subsampling must round the chroma plane up to
+    // 8x5, not allocate it at the full luma 15x9.
+    let frame = Frame::<u8>::new(
+      Format::new(1, 1, 8, PixelRange::Full),
+      15,
+      9
+    );
+    assert_eq!(frame.plane(0).cfg.width, 15);
+    assert_eq!(frame.plane(0).cfg.height, 9);
+    assert_eq!(frame.plane(1).cfg.width, 8);
+    assert_eq!(frame.plane(1).cfg.height, 5);
+    assert_eq!(frame.plane(2).cfg.width, 8);
+    assert_eq!(frame.plane(2).cfg.height, 5);
+  }
+
+  #[test]
+  fn new_frame_passes_padding_validation() {
+    let frame =
+      Frame::<u8>::new(Format::new(1, 1, 8, PixelRange::Full), 16, 16);
+    assert!(frame.validate_padding().is_ok());
+  }
+
+  #[test]
+  fn validate_padding_reports_corrupted_border() {
+    let mut frame =
+      Frame::<u8>::new(Format::new(1, 1, 8, PixelRange::Full), 16, 16);
+    let y_plane = frame.plane_mut(0);
+    let corrupt_at = 0;
+    y_plane.data[corrupt_at] = y_plane.data[corrupt_at].wrapping_add(1);
+    let err = frame.validate_padding().unwrap_err();
+    assert_eq!(err.plane, 0);
+  }
+}
+
+fn validate_plane_padding<T: Pixel>(plane: &Plane<T>) -> Result<(), usize> {
+  let cfg = &plane.cfg;
+  let stride = cfg.stride;
+  let xorigin = cfg.xorigin;
+  let yorigin = cfg.yorigin;
+  let width = cfg.width;
+  let height = cfg.height;
+
+  for y in 0..height {
+    let row_start = (yorigin + y) * stride;
+    let row = &plane.data[row_start..row_start + stride];
+
+    let left_val = row[xorigin];
+    if let Some(i) = row[..xorigin].iter().position(|&v| v != left_val) {
+      return Err(row_start + i);
+    }
+
+    if xorigin + width < stride {
+      let right_val = row[xorigin + width - 1];
+      if let Some(i) =
+        row[xorigin + width..].iter().position(|&v| v != right_val)
+      {
+        return Err(row_start + xorigin + width + i);
+      }
+    }
+  }
+
+  if yorigin > 0 {
+    let first_row_start = yorigin * stride;
+    let first_row = &plane.data[first_row_start..first_row_start + stride];
+    for y in 0..yorigin {
+      let row_start = y * stride;
+      if plane.data[row_start..row_start + stride] != *first_row {
+        return Err(row_start);
+      }
+    }
+  }
+
+  let bottom_start = yorigin + height;
+  if bottom_start < cfg.alloc_height {
+    let last_row_start = (bottom_start - 1) * stride;
+    let last_row = &plane.data[last_row_start..last_row_start + stride];
+    for y in bottom_start..cfg.alloc_height {
+      let row_start = y * stride;
+      if plane.data[row_start..row_start + stride] != *last_row {
+        return Err(row_start);
+      }
+    }
+  }
+
+  Ok(())
+}
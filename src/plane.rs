@@ -10,6 +10,9 @@
 use std::iter::FusedIterator;
 use std::fmt::{Debug, Display, Formatter};
 use std::mem;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
 
 use crate::util::*;
 
@@ -35,6 +38,77 @@ pub struct PlaneOffset {
   pub y: isize
 }
 
+/// Byte order of multi-byte pixel samples being imported via
+/// `Plane::copy_from_raw_u8`/`copy_from_raw_u8_with_depth`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelEndianness {
+  /// The running platform's native byte order.
+  Native,
+  Little,
+  Big
+}
+
+impl PixelEndianness {
+  #[cfg(target_endian = "little")]
+  const NATIVE: PixelEndianness = PixelEndianness::Little;
+  #[cfg(target_endian = "big")]
+  const NATIVE: PixelEndianness = PixelEndianness::Big;
+
+  fn resolve(self) -> PixelEndianness {
+    match self {
+      PixelEndianness::Native => Self::NATIVE,
+      other => other
+    }
+  }
+}
+
+/// A separable set of filter taps (offsets in pixels, relative to a
+/// center sample, paired with integer weights) used by
+/// `Plane::downsample_from_with`.
+#[derive(Debug, Clone)]
+struct Taps {
+  offsets: Vec<isize>,
+  weights: Vec<i32>
+}
+
+impl Taps {
+  fn scale(&self) -> i32 {
+    self.weights.iter().sum()
+  }
+}
+
+/// A triangular ("tent") filter of support `2 * ratio - 1`, i.e. the
+/// self-convolution of a `ratio`-wide box -- the standard separable
+/// bilinear downsampling kernel for an integer ratio.
+fn tent_taps(ratio: usize) -> Taps {
+  let r = ratio as isize;
+  let offsets: Vec<isize> = (-(r - 1)..=(r - 1)).collect();
+  let weights = offsets.iter().map(|&i| (r - i.abs()) as i32).collect();
+  Taps { offsets, weights }
+}
+
+/// A fixed 5-tap binomial approximation of a windowed Lanczos/Gaussian
+/// kernel.
+fn lanczos5_taps() -> Taps {
+  Taps { offsets: vec![-2, -1, 0, 1, 2], weights: vec![1, 4, 6, 4, 1] }
+}
+
+/// Separable filter used to build a reduced-resolution `Plane` from a
+/// full-resolution one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownsampleFilter {
+  /// A plain `ratio`x`ratio` box average -- the filter `downsample_from`
+  /// has always used.
+  Box,
+  /// A separable triangular ("tent") filter, the standard bilinear
+  /// downsampling kernel.
+  Bilinear,
+  /// A separable 5-tap binomial approximation of a windowed
+  /// Lanczos/Gaussian kernel, for higher-quality reduction than `Box` or
+  /// `Bilinear`.
+  Lanczos
+}
+
 #[derive(Clone)]
 pub struct Plane<T: Pixel> {
   pub data: Vec<T>,
@@ -163,10 +237,55 @@ impl<T: Pixel> Plane<T> {
     &mut self.data[i..]
   }
 
+  /// Imports pixels from a raw byte buffer, assuming the source samples
+  /// already fill this plane's coded bit depth (no shift).
   pub fn copy_from_raw_u8(
-    &mut self, source: &[u8], source_stride: usize, source_bytewidth: usize
+    &mut self, source: &[u8], source_stride: usize, source_bytewidth: usize,
+    endianness: PixelEndianness
+  ) {
+    // `Plane` itself has no notion of coded bit depth (only `T`'s
+    // container width), so there is no depth to derive here -- pass the
+    // same value as both "source" and "target" depth, which always
+    // yields a no-op shift regardless of what that depth actually is.
+    let depth = 8 * source_bytewidth;
+    self.copy_from_raw_u8_with_depth(
+      source,
+      source_stride,
+      source_bytewidth,
+      endianness,
+      depth,
+      depth
+    );
+  }
+
+  /// Imports pixels from a raw byte buffer whose samples are `source_depth`
+  /// bits wide, shifting them up into `plane_depth` (this plane's *coded*
+  /// bit depth, which `Plane` does not track itself and so must be passed
+  /// in by the caller). This is what lets e.g. 10-bit samples packed in a
+  /// 16-bit (`source_bytewidth == 2`) container land correctly in a
+  /// `Plane<u16>` built for a 10-bit depth, rather than being left-shifted
+  /// as if the container width were the coded depth.
+  ///
+  /// When `source_bytewidth == 2`, `T` is 16-bit, the source is in the
+  /// platform's native byte order and `u16`-aligned, and no shift is
+  /// needed, the source row is reinterpreted directly as `&[u16]` rather
+  /// than assembled byte-by-byte. Non-native byte order, misaligned
+  /// buffers, mixed widths, or a depth shift fall back to a per-pixel path
+  /// that reads samples via `u16::from_le_bytes`/`from_be_bytes`.
+  pub fn copy_from_raw_u8_with_depth(
+    &mut self, source: &[u8], source_stride: usize, source_bytewidth: usize,
+    endianness: PixelEndianness, source_depth: usize, plane_depth: usize
   ) {
+    let endianness = endianness.resolve();
+    assert!(
+      source_depth <= plane_depth,
+      "source depth ({}) does not fit in this plane's depth ({})",
+      source_depth,
+      plane_depth
+    );
+    let shift = plane_depth - source_depth;
     let stride = self.cfg.stride;
+
     for (self_row, source_row) in self
       .data_origin_mut()
       .chunks_mut(stride)
@@ -176,49 +295,282 @@ impl<T: Pixel> Plane<T> {
         1 => for (self_pixel, source_pixel) in
           self_row.iter_mut().zip(source_row.iter())
         {
-          *self_pixel = T::cast_from(*source_pixel);
+          *self_pixel = T::cast_from((*source_pixel as u16) << shift);
         },
         2 => {
           assert!(mem::size_of::<T>() >= 2, "source bytewidth ({}) cannot fit in Plane<u8>", source_bytewidth);
-          for (self_pixel, bytes) in
-            self_row.iter_mut().zip(source_row.chunks(2))
-          {
-            *self_pixel = T::cast_from(u16::cast_from(bytes[1]) << 8 | u16::cast_from(bytes[0]));
+          let aligned = (source_row.as_ptr() as usize)
+            .trailing_zeros() as usize
+            >= mem::align_of::<u16>().trailing_zeros() as usize;
+          if shift == 0 && endianness == PixelEndianness::NATIVE && aligned {
+            // Zero-copy reinterpretation: the source row is already an
+            // array of native-endian, full-depth u16 samples, so no
+            // per-pixel shifting/assembly is needed.
+            let len = self_row.len().min(source_row.len() / 2);
+            let samples = unsafe {
+              std::slice::from_raw_parts(source_row.as_ptr() as *const u16, len)
+            };
+            for (self_pixel, &sample) in self_row.iter_mut().zip(samples) {
+              *self_pixel = T::cast_from(sample);
+            }
+          } else {
+            for (self_pixel, bytes) in
+              self_row.iter_mut().zip(source_row.chunks(2))
+            {
+              let sample = match endianness {
+                PixelEndianness::Big => u16::from_be_bytes([bytes[0], bytes[1]]),
+                _ => u16::from_le_bytes([bytes[0], bytes[1]])
+              };
+              *self_pixel = T::cast_from(sample << shift);
+            }
           }
         },
 
-        _ => {}
+        _ => panic!("unsupported source bytewidth: {}", source_bytewidth)
       }
     }
   }
 
+  /// Downsamples `src` into `self` at a fixed 2x ratio using a plain box
+  /// average, preserved for callers that predate `downsample_from_with`.
   pub fn downsample_from(&mut self, src: &Plane<T>) {
+    self.downsample_from_with(src, 2, DownsampleFilter::Box);
+  }
+
+  /// Downsamples `src` into `self` by an integer `ratio` (`self`'s
+  /// dimensions must be `src`'s divided by `ratio`), using the separable
+  /// `filter`. This generalizes `downsample_from`, which is always a 2x
+  /// box average, into a building block for `build_pyramid`.
+  pub fn downsample_from_with(
+    &mut self, src: &Plane<T>, ratio: usize, filter: DownsampleFilter
+  ) {
+    // `<=` rather than `==` so an odd source dimension can still be
+    // downsampled -- the trailing partial block of source pixels is
+    // simply left out of the average, same as a plain image resize would
+    // crop it.
+    assert!(self.cfg.width * ratio <= src.cfg.width);
+    assert!(self.cfg.height * ratio <= src.cfg.height);
+
+    match filter {
+      DownsampleFilter::Box => self.downsample_box(src, ratio),
+      DownsampleFilter::Bilinear => {
+        let taps = tent_taps(ratio);
+        self.downsample_separable(src, ratio, &taps, &taps);
+      }
+      DownsampleFilter::Lanczos => {
+        let taps = lanczos5_taps();
+        self.downsample_separable(src, ratio, &taps, &taps);
+      }
+    }
+  }
+
+  /// Reads a pixel at a possibly-negative or past-the-edge coordinate,
+  /// relying on the plane's existing padded border (`xorigin`/`yorigin`)
+  /// so interior filter taps never need a bounds branch.
+  fn p_isize(&self, x: isize, y: isize) -> T {
+    let xi = (x + self.cfg.xorigin as isize) as usize;
+    let yi = (y + self.cfg.yorigin as isize) as usize;
+    self.data[yi * self.cfg.stride + xi]
+  }
+
+  fn downsample_box(&mut self, src: &Plane<T>, ratio: usize) {
     let width = self.cfg.width;
     let height = self.cfg.height;
-
-    assert!(width * 2 == src.cfg.width);
-    assert!(height * 2 == src.cfg.height);
+    let count = (ratio * ratio) as u32;
+    let half = count / 2;
 
     for row in 0..height {
       let mut dst_slice = self.mut_slice(&PlaneOffset{ x: 0, y: row as isize });
       let dst = dst_slice.as_mut_slice();
 
       for col in 0..width {
-        let mut sum = 0;
-        sum += u32::cast_from(src.p(2 * col, 2 * row));
-        sum += u32::cast_from(src.p(2 * col + 1, 2 * row));
-        sum += u32::cast_from(src.p(2 * col, 2 * row + 1));
-        sum += u32::cast_from(src.p(2 * col + 1, 2 * row + 1));
-        let avg = (sum + 2) >> 2;
-        dst[col] = T::cast_from(avg);
+        let mut sum = 0u32;
+        for dy in 0..ratio {
+          for dx in 0..ratio {
+            sum += u32::cast_from(src.p(col * ratio + dx, row * ratio + dy));
+          }
+        }
+        // Keeps the rounding-to-nearest behavior of the original 2x box
+        // path, generalized from `(sum + 2) >> 2` to an arbitrary count.
+        dst[col] = T::cast_from((sum + half) / count);
       }
     }
   }
 
+  /// Applies `h_taps` horizontally into a scratch row, then `v_taps`
+  /// vertically across the scratch rows needed for one output row, for
+  /// every output row. Sample coordinates outside the active region are
+  /// read through the plane's padded border via `p_isize`.
+  fn downsample_separable(
+    &mut self, src: &Plane<T>, ratio: usize, h_taps: &Taps, v_taps: &Taps
+  ) {
+    let width = self.cfg.width;
+    let height = self.cfg.height;
+    let h_scale = h_taps.scale();
+    let v_scale = v_taps.scale();
+    let h_half = h_scale / 2;
+    let v_half = v_scale / 2;
+
+    // The true center of a `ratio`-wide run of source samples is
+    // `(ratio - 1) / 2`, which is a half-integer whenever `ratio` is
+    // even (e.g. 0.5 for ratio 2). `center` is that value's floor; when
+    // `halfway` is set, every tap sample is additionally averaged with
+    // its next-door neighbor to land exactly on the true half-integer
+    // center instead of being biased a full sample off it -- for ratio 2
+    // this reproduces the plain box path's sample pair exactly.
+    let center = ((ratio - 1) / 2) as isize;
+    let halfway = ratio % 2 == 0;
+
+    let h_filter_row = |sy: isize, out: &mut [i32]| {
+      for col in 0..width {
+        let base_x = (col * ratio) as isize + center;
+        let mut sum = 0i32;
+        for (&dx, &w) in h_taps.offsets.iter().zip(&h_taps.weights) {
+          let x = base_x + dx;
+          let sample = if halfway {
+            let a = u32::cast_from(src.p_isize(x, sy)) as i32;
+            let b = u32::cast_from(src.p_isize(x + 1, sy)) as i32;
+            (a + b + 1) / 2
+          } else {
+            u32::cast_from(src.p_isize(x, sy)) as i32
+          };
+          sum += w * sample;
+        }
+        out[col] = (sum + h_half) / h_scale;
+      }
+    };
+
+    // Horizontally-filtered source rows, memoized by source row index so
+    // each is computed at most once per output row even though `halfway`
+    // needs every vertical tap's row and its next-door neighbor too. The
+    // backing buffers are allocated once -- up front -- and only their
+    // `row_tags` are reset between output rows, so no `Vec<i32>` is ever
+    // allocated inside the row loop.
+    let cache_size = v_taps.offsets.len() + 1;
+    let mut row_tags: Vec<Option<isize>> = vec![None; cache_size];
+    let mut row_bufs: Vec<Vec<i32>> =
+      (0..cache_size).map(|_| vec![0i32; width]).collect();
+    let ensure_row =
+      |tags: &mut Vec<Option<isize>>, bufs: &mut Vec<Vec<i32>>, sy: isize| {
+        if let Some(idx) = tags.iter().position(|&t| t == Some(sy)) {
+          return idx;
+        }
+        let idx = tags
+          .iter()
+          .position(|t| t.is_none())
+          .expect("row cache undersized for this filter's support");
+        h_filter_row(sy, &mut bufs[idx]);
+        tags[idx] = Some(sy);
+        idx
+      };
+
+    // One reusable row buffer per vertical tap, overwritten on every
+    // output row instead of reallocated.
+    let mut combined: Vec<Vec<i32>> =
+      vec![vec![0i32; width]; v_taps.offsets.len()];
+
+    for row in 0..height {
+      let base_y = (row * ratio) as isize + center;
+      for tag in row_tags.iter_mut() {
+        *tag = None;
+      }
+
+      for (tap_idx, &dy) in v_taps.offsets.iter().enumerate() {
+        let sy = base_y + dy;
+        let ia = ensure_row(&mut row_tags, &mut row_bufs, sy);
+        if halfway {
+          let ib = ensure_row(&mut row_tags, &mut row_bufs, sy + 1);
+          for col in 0..width {
+            combined[tap_idx][col] =
+              (row_bufs[ia][col] + row_bufs[ib][col] + 1) / 2;
+          }
+        } else {
+          combined[tap_idx].copy_from_slice(&row_bufs[ia]);
+        }
+      }
+
+      let mut dst_slice = self.mut_slice(&PlaneOffset { x: 0, y: row as isize });
+      let dst = dst_slice.as_mut_slice();
+      for col in 0..width {
+        let mut sum = 0i32;
+        for (row_val, &w) in combined.iter().zip(&v_taps.weights) {
+          sum += w * row_val[col];
+        }
+        dst[col] = T::cast_from(((sum + v_half) / v_scale).max(0) as u32);
+      }
+    }
+  }
+
+  /// Builds a pyramid of `levels` successively half-sized planes below
+  /// `self`, each downsampled from the previous with `filter`. Lets motion
+  /// search and scene-change detection operate on higher-quality reduced
+  /// resolutions instead of only the aliased box-filtered half-res image.
+  pub fn build_pyramid(
+    &self, levels: usize, filter: DownsampleFilter
+  ) -> Vec<Plane<T>> {
+    let mut pyramid: Vec<Plane<T>> = Vec::with_capacity(levels);
+    for i in 0..levels {
+      let src = if i == 0 { self } else { &pyramid[i - 1] };
+      let width = src.cfg.width / 2;
+      let height = src.cfg.height / 2;
+      // However much `src`'s own padding has shrunk by, every level still
+      // needs at least 2 columns/rows of border on each side -- the
+      // widest support any of our separable filters' taps reach.
+      let xpad = (src.cfg.xpad / 2).max(2);
+      let ypad = (src.cfg.ypad / 2).max(2);
+      let mut level =
+        Plane::new(width, height, src.cfg.xdec, src.cfg.ydec, xpad, ypad);
+      level.downsample_from_with(src, 2, filter);
+      // Replicate the border into the padding region so that the *next*
+      // pyramid level's filter taps (which read through `p_isize` into
+      // this plane's padding) see edge-replicated samples rather than
+      // `Plane::new`'s uninitialized fill value.
+      level.pad(width << level.cfg.xdec, height << level.cfg.ydec);
+      pyramid.push(level);
+    }
+    pyramid
+  }
+
   /// Iterates over the pixels in the `Plane`, skipping stride data.
   pub fn iter(&self) -> PlaneIter<'_, T> {
     PlaneIter::new(self)
   }
+
+  /// A borrowed, `imgref`-style view of the active (non-padded) region of
+  /// this plane, for interop with the wider strided-2D-image ecosystem.
+  pub fn as_img_ref(&self) -> ImgRef<'_, T> {
+    ImgRef {
+      buf: self.data_origin(),
+      width: self.cfg.width,
+      height: self.cfg.height,
+      stride: self.cfg.stride
+    }
+  }
+
+  /// As `as_img_ref`, but mutable.
+  pub fn as_img_ref_mut(&mut self) -> ImgRefMut<'_, T> {
+    let (width, height, stride) = (self.cfg.width, self.cfg.height, self.cfg.stride);
+    ImgRefMut { buf: self.data_origin_mut(), width, height, stride }
+  }
+}
+
+/// A borrowed strided-2D-image view over a `Plane`'s active region,
+/// analogous to the `imgref` crate's `ImgRef`.
+#[derive(Debug, Clone, Copy)]
+pub struct ImgRef<'a, T: Pixel> {
+  pub buf: &'a [T],
+  pub width: usize,
+  pub height: usize,
+  pub stride: usize
+}
+
+/// As `ImgRef`, but mutable.
+#[derive(Debug)]
+pub struct ImgRefMut<'a, T: Pixel> {
+  pub buf: &'a mut [T],
+  pub width: usize,
+  pub height: usize,
+  pub stride: usize
 }
 
 #[derive(Debug)]
@@ -350,6 +702,28 @@ impl<'a, T: Pixel> PlaneSlice<'a, T> {
     IterWidth { ps: *self, width }
   }
 
+  /// Iterates over the rows from this slice to the edge of the plane,
+  /// each yielded as a `&[T]` of exactly the remaining width -- unlike
+  /// `iter_width`, the caller does not need to know the width up front.
+  pub fn rows_iter(&self) -> std::iter::Take<IterWidth<'a, T>> {
+    let width = (self.plane.cfg.width as isize - self.x).max(0) as usize;
+    let rows = (self.plane.cfg.height as isize - self.y).max(0) as usize;
+    self.iter_width(width).take(rows)
+  }
+
+  /// A sub-window of this slice starting `(x, y)` pixels into it. The
+  /// parent's stride is preserved, so the window is a plain re-origining
+  /// of the same backing data rather than a copy. The returned
+  /// `PlaneSlice` holds only the new origin: `w` and `h` are checked
+  /// against the plane's size in debug builds but are not kept, so e.g.
+  /// `rows_iter` on the result runs to the plane's edges, not the window's.
+  pub fn sub_image(&self, x: usize, y: usize, w: usize, h: usize) -> PlaneSlice<'a, T> {
+    let sub = self.subslice(x, y);
+    debug_assert!(sub.x + w as isize <= self.plane.cfg.width as isize);
+    debug_assert!(sub.y + h as isize <= self.plane.cfg.height as isize);
+    sub
+  }
+
   pub fn subslice(&self, xo: usize, yo: usize) -> PlaneSlice<'a, T> {
     PlaneSlice {
       plane: self.plane,
@@ -447,3 +821,386 @@ impl<'a, T: Pixel> PlaneMutSlice<'a, T> {
     self.plane.data[new_y * self.plane.cfg.stride + new_x]
   }
 }
+
+/// A rectangular region of a `Plane`, in pixel coordinates relative to the
+/// plane's (unpadded) origin.
+#[derive(Debug, Clone, Copy)]
+pub struct Rect {
+  pub x: usize,
+  pub y: usize,
+  pub width: usize,
+  pub height: usize
+}
+
+/// The set of linear index ranges into `Plane::data` that a single
+/// `PlaneRegionMut` is allowed to touch, one range per row.
+type Bounds = Vec<Range<usize>>;
+
+fn bounds_overlap(a: &Bounds, b: &Bounds) -> bool {
+  a.iter().any(|ra| b.iter().any(|rb| ra.start < rb.end && rb.start < ra.end))
+}
+
+/// Tracks the `Bounds` of every `PlaneRegionMut` currently handed out from a
+/// given `Plane`, so that overlapping regions can be caught in debug builds
+/// instead of silently corrupting each other across threads.
+#[derive(Default)]
+struct DisjointTracker {
+  next_id: u64,
+  active: HashMap<u64, Bounds>
+}
+
+impl DisjointTracker {
+  /// Registers `bounds`, panicking in debug builds if they intersect any
+  /// region that is already active. Returns the id to later remove them.
+  fn acquire(&mut self, bounds: Bounds) -> u64 {
+    if cfg!(debug_assertions) {
+      for existing in self.active.values() {
+        assert!(
+          !bounds_overlap(existing, &bounds),
+          "overlapping PlaneRegionMut requested from the same Plane"
+        );
+      }
+    }
+    let id = self.next_id;
+    self.next_id += 1;
+    self.active.insert(id, bounds);
+    id
+  }
+
+  fn release(&mut self, id: u64) {
+    self.active.remove(&id);
+  }
+}
+
+fn rect_bounds(cfg: &PlaneConfig, rect: Rect) -> Bounds {
+  (0..rect.height)
+    .map(|row| {
+      let y = cfg.yorigin + rect.y + row;
+      let x = cfg.xorigin + rect.x;
+      let start = y * cfg.stride + x;
+      start..start + rect.width
+    })
+    .collect()
+}
+
+/// A read-only view of a rectangular region of a `Plane`, analogous to
+/// `PlaneSlice` but clamped to the region rather than the whole plane.
+#[derive(Clone, Copy)]
+pub struct PlaneRegion<'a, T: Pixel> {
+  data: *const T,
+  stride: usize,
+  pub rect: Rect,
+  phantom: std::marker::PhantomData<&'a T>
+}
+
+unsafe impl<'a, T: Pixel> Send for PlaneRegion<'a, T> {}
+unsafe impl<'a, T: Pixel> Sync for PlaneRegion<'a, T> {}
+
+impl<'a, T: Pixel> PlaneRegion<'a, T> {
+  /// Returns the row at `y` within the region, exactly `rect.width` long.
+  pub fn row(&self, y: usize) -> &'a [T] {
+    assert!(y < self.rect.height);
+    let base = y * self.stride;
+    unsafe {
+      std::slice::from_raw_parts(self.data.add(base), self.rect.width)
+    }
+  }
+
+  pub fn rows_iter(&self) -> impl Iterator<Item = &'a [T]> {
+    let this = *self;
+    (0..this.rect.height).map(move |y| this.row(y))
+  }
+}
+
+/// A mutable, disjointness-checked view of a rectangular region of a
+/// `Plane`'s backing buffer, obtained from `Plane::split_regions`. Multiple
+/// `PlaneRegionMut`s handed out for the same `Plane` are guaranteed (in
+/// debug builds, via a runtime assertion) never to overlap, so worker
+/// threads can write to them concurrently without copying the plane.
+pub struct PlaneRegionMut<'a, T: Pixel> {
+  data: *mut T,
+  stride: usize,
+  pub rect: Rect,
+  tracker: Arc<Mutex<DisjointTracker>>,
+  id: u64,
+  phantom: std::marker::PhantomData<&'a mut T>
+}
+
+unsafe impl<'a, T: Pixel> Send for PlaneRegionMut<'a, T> {}
+
+impl<'a, T: Pixel> PlaneRegionMut<'a, T> {
+  pub fn row(&self, y: usize) -> &[T] {
+    assert!(y < self.rect.height);
+    let base = y * self.stride;
+    unsafe { std::slice::from_raw_parts(self.data.add(base), self.rect.width) }
+  }
+
+  pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+    assert!(y < self.rect.height);
+    let base = y * self.stride;
+    unsafe {
+      std::slice::from_raw_parts_mut(self.data.add(base), self.rect.width)
+    }
+  }
+
+  pub fn rows_iter_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+    let stride = self.stride;
+    let width = self.rect.width;
+    let height = self.rect.height;
+    let data = self.data;
+    (0..height).map(move |y| unsafe {
+      std::slice::from_raw_parts_mut(data.add(y * stride), width)
+    })
+  }
+}
+
+impl<'a, T: Pixel> Drop for PlaneRegionMut<'a, T> {
+  fn drop(&mut self) {
+    self.tracker.lock().unwrap().release(self.id);
+  }
+}
+
+impl<T: Pixel> Plane<T> {
+  /// Hands out one non-overlapping, independently mutable `PlaneRegionMut`
+  /// per entry in `rects`, all backed by this plane's single `data` buffer.
+  /// This lets separate worker threads encode disjoint tiles in place
+  /// instead of copying each tile's pixels in and back out.
+  ///
+  /// `rects` must lie within the plane and describe pairwise-disjoint
+  /// regions; both are validated up front (disjointness is also re-checked
+  /// per region in debug builds) and violations panic rather than silently
+  /// aliasing or reading out of bounds.
+  pub fn split_regions(&mut self, rects: &[Rect]) -> Vec<PlaneRegionMut<'_, T>> {
+    for (i, r) in rects.iter().enumerate() {
+      assert!(
+        r.x + r.width <= self.cfg.width && r.y + r.height <= self.cfg.height,
+        "split_regions: rects[{}] ({:?}) is out of bounds for a {}x{} plane",
+        i,
+        r,
+        self.cfg.width,
+        self.cfg.height
+      );
+    }
+
+    let bounds: Vec<Bounds> =
+      rects.iter().map(|&r| rect_bounds(&self.cfg, r)).collect();
+    for i in 0..bounds.len() {
+      for j in i + 1..bounds.len() {
+        assert!(
+          !bounds_overlap(&bounds[i], &bounds[j]),
+          "split_regions: rects[{}] and rects[{}] overlap",
+          i,
+          j
+        );
+      }
+    }
+
+    let tracker = Arc::new(Mutex::new(DisjointTracker::default()));
+    let stride = self.cfg.stride;
+    let xorigin = self.cfg.xorigin;
+    let yorigin = self.cfg.yorigin;
+    // Safety: `data` is derived from the single `&mut self` borrow this
+    // function holds for its whole body, not from a shared reference, so
+    // there is no separate aliasing `&[T]`/`&mut [T]` live anywhere else
+    // the data could also be reached through. `rects` has just been
+    // proven pairwise-disjoint (and in bounds) above, and the per-region
+    // `Bounds` re-registered below are checked again in debug builds, so
+    // subdividing that one unique borrow into per-rect raw pointers -- the
+    // same pattern `<[T]>::split_at_mut` uses internally -- never hands
+    // out two guards that can reach the same element.
+    let data = self.data.as_mut_ptr();
+    rects
+      .iter()
+      .zip(bounds)
+      .map(|(&rect, b)| {
+        let id = tracker.lock().unwrap().acquire(b);
+        PlaneRegionMut {
+          data: unsafe {
+            data.add((yorigin + rect.y) * stride + xorigin + rect.x)
+          },
+          stride,
+          rect,
+          tracker: tracker.clone(),
+          id,
+          phantom: std::marker::PhantomData
+        }
+      })
+      .collect()
+  }
+
+  /// Returns a read-only view of `rect` within this plane.
+  pub fn region(&self, rect: Rect) -> PlaneRegion<'_, T> {
+    let base = (self.cfg.yorigin + rect.y) * self.cfg.stride
+      + self.cfg.xorigin
+      + rect.x;
+    PlaneRegion {
+      data: self.data[base..].as_ptr(),
+      stride: self.cfg.stride,
+      rect,
+      phantom: std::marker::PhantomData
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn split_regions_writes_land_in_the_right_place() {
+    let mut plane = Plane::<u8>::new(4, 4, 0, 0, 0, 0);
+    {
+      let mut regions = plane.split_regions(&[
+        Rect { x: 0, y: 0, width: 2, height: 4 },
+        Rect { x: 2, y: 0, width: 2, height: 4 }
+      ]);
+      let (left, right) = regions.split_at_mut(1);
+      for row in 0..4 {
+        left[0].row_mut(row).copy_from_slice(&[1, 2]);
+        right[0].row_mut(row).copy_from_slice(&[3, 4]);
+      }
+    }
+    for row in 0..4 {
+      assert_eq!(plane.p(0, row), 1);
+      assert_eq!(plane.p(1, row), 2);
+      assert_eq!(plane.p(2, row), 3);
+      assert_eq!(plane.p(3, row), 4);
+    }
+  }
+
+  #[test]
+  #[should_panic(expected = "overlap")]
+  fn split_regions_panics_on_overlapping_rects() {
+    let mut plane = Plane::<u8>::new(4, 4, 0, 0, 0, 0);
+    plane.split_regions(&[
+      Rect { x: 0, y: 0, width: 3, height: 4 },
+      Rect { x: 2, y: 0, width: 2, height: 4 }
+    ]);
+  }
+
+  #[test]
+  #[should_panic(expected = "out of bounds")]
+  fn split_regions_panics_on_out_of_bounds_rect() {
+    let mut plane = Plane::<u8>::new(4, 4, 0, 0, 0, 0);
+    plane.split_regions(&[Rect { x: 0, y: 0, width: 5, height: 4 }]);
+  }
+
+  #[test]
+  fn copy_from_raw_u8_does_not_shift() {
+    let mut plane = Plane::<u8>::new(4, 1, 0, 0, 0, 0);
+    let source = [10u8, 20, 30, 40];
+    plane.copy_from_raw_u8(&source, 4, 1, PixelEndianness::Native);
+    assert_eq!(
+      (0..4).map(|x| plane.p(x, 0)).collect::<Vec<_>>(),
+      vec![10, 20, 30, 40]
+    );
+  }
+
+  #[test]
+  fn copy_from_raw_u8_with_depth_shifts_narrower_source_up() {
+    // A 10-bit source packed into a 16-bit container, imported into a
+    // plane coded at the full 16-bit depth, must be left-shifted by
+    // 16 - 10 = 6 rather than treated as already filling 16 bits.
+    let mut plane = Plane::<u16>::new(2, 1, 0, 0, 0, 0);
+    let source: [u8; 4] = [0x05, 0x00, 0xff, 0x03]; // 5, 1023 (LE)
+    plane.copy_from_raw_u8_with_depth(
+      &source,
+      4,
+      2,
+      PixelEndianness::Little,
+      10,
+      16
+    );
+    assert_eq!(plane.p(0, 0), 5 << 6);
+    assert_eq!(plane.p(1, 0), 1023 << 6);
+  }
+
+  #[test]
+  fn copy_from_raw_u8_with_depth_respects_endianness() {
+    let mut plane = Plane::<u16>::new(1, 1, 0, 0, 0, 0);
+    let source: [u8; 2] = [0x01, 0x02];
+    plane.copy_from_raw_u8_with_depth(
+      &source,
+      2,
+      2,
+      PixelEndianness::Big,
+      16,
+      16
+    );
+    assert_eq!(plane.p(0, 0), 0x0102);
+  }
+
+  #[test]
+  fn copy_from_raw_u8_with_depth_imports_aligned_native_row_unchanged() {
+    // An aligned, native-endian, no-shift u16 row -- the input the
+    // zero-copy reinterpret path handles -- must be imported with its
+    // sample values unchanged.
+    let mut plane = Plane::<u16>::new(3, 1, 0, 0, 0, 0);
+    let source: [u16; 3] = [100, 2000, 30000];
+    let source_bytes = unsafe {
+      std::slice::from_raw_parts(source.as_ptr() as *const u8, 6)
+    };
+    plane.copy_from_raw_u8_with_depth(
+      source_bytes,
+      6,
+      2,
+      PixelEndianness::Native,
+      16,
+      16
+    );
+    assert_eq!(
+      (0..3).map(|x| plane.p(x, 0)).collect::<Vec<_>>(),
+      vec![100, 2000, 30000]
+    );
+  }
+
+  #[test]
+  fn sub_image_and_rows_iter_see_the_right_window() {
+    let mut plane = Plane::<u8>::new(4, 4, 0, 0, 0, 0);
+    for y in 0..4 {
+      for x in 0..4 {
+        let mut s = plane.mut_slice(&PlaneOffset { x: 0, y: y as isize });
+        s.as_mut_slice_w_width(4)[x] = (y * 4 + x) as u8;
+      }
+    }
+
+    let full = plane.slice(&PlaneOffset { x: 0, y: 0 });
+    let window = full.sub_image(1, 1, 2, 2);
+    assert_eq!(window.p(0, 0), 5);
+    assert_eq!(window.p(1, 0), 6);
+    assert_eq!(window.p(0, 1), 9);
+
+    let rows: Vec<&[u8]> = full.rows_iter().collect();
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows[0], &[0, 1, 2, 3]);
+    assert_eq!(rows[3], &[12, 13, 14, 15]);
+  }
+
+  /// Regression test for the half-pixel bias `downsample_separable` used
+  /// to have: with a symmetric source row, a correctly centered filter
+  /// must produce a symmetric downsampled row, whichever side it reads
+  /// from first.
+  #[test]
+  fn downsample_separable_ratio2_is_not_position_biased() {
+    let width = 8;
+    let height = 2;
+    let row = [0u8, 10, 20, 30, 30, 20, 10, 0];
+    let mut src = Plane::<u8>::new(width, height, 0, 0, 4, 4);
+    for y in 0..height {
+      for x in 0..width {
+        let v = row[x];
+        let mut s = src.mut_slice(&PlaneOffset { x: 0, y: y as isize });
+        s.as_mut_slice_w_width(width)[x] = v;
+      }
+    }
+    src.pad(width, height);
+
+    let mut dst = Plane::<u8>::new(width / 2, height / 2, 0, 0, 0, 0);
+    dst.downsample_from_with(&src, 2, DownsampleFilter::Bilinear);
+
+    for y in 0..height / 2 {
+      assert_eq!(dst.p(0, y), dst.p(3, y));
+      assert_eq!(dst.p(1, y), dst.p(2, y));
+    }
+  }
+}